@@ -1,30 +1,35 @@
 extern crate rs_release;
 
-use rs_release::{get_os_release, parse_os_release, parse_os_release_str};
+use rs_release::OsRelease;
 use std::env;
+use std::str::FromStr;
 
 fn main() {
-    let mut args = env::args();
-
-    let os_release: Result<Vec<_>, _> = if let Some(os_release_path) = args.nth(1) {
-        parse_os_release(os_release_path).and_then(|os_release| os_release.collect())
+    let os_release = if let Some(os_release_path) = env::args().nth(1) {
+        std::fs::read_to_string(os_release_path)
+            .map_err(rs_release::OsReleaseError::from)
+            .and_then(|data| OsRelease::from_str(&data))
     } else {
-        get_os_release().and_then(|os_release| os_release.collect())
+        OsRelease::new()
     };
 
     match os_release {
         Ok(os_release) => {
             println!("Parsed os-release:");
-            for (k, v) in os_release {
-                println!("{}={}", k, v);
-            }
+            println!("id: {:?}", os_release.id);
+            println!("name: {:?}", os_release.name);
+            println!("pretty_name: {:?}", os_release.pretty_name);
+            println!("version: {:?}", os_release.version);
+            println!("version_id: {:?}", os_release.version_id);
+            println!("extra: {:?}", os_release.extra);
         }
         Err(e) => println!("ERROR: {:?}", e),
     }
 
     // You could also parse data from a string
     println!("Parsed os-release from &str:");
-    for (k, v) in parse_os_release_str("NAME = Fedora").filter_map(Result::ok) {
-        println!("{}={}", k, v);
+    match OsRelease::from_str("NAME = Fedora") {
+        Ok(os_release) => println!("name: {:?}", os_release.name),
+        Err(e) => println!("ERROR: {:?}", e),
     }
 }
@@ -1,5 +1,6 @@
 extern crate rs_release;
 
+use rs_release::OsRelease;
 use std::process::Command;
 
 #[derive(Debug)]
@@ -8,14 +9,8 @@ enum Error {
     ReadError,
 }
 
-fn get_os_id() -> Result<String, Error> {
-    let os_release = rs_release::get_os_release().map_err(|_| Error::ReadError)?;
-
-    os_release
-        .filter_map(Result::ok)
-        .find(|(key, _)| key == "ID")
-        .map(|(_, value)| value)
-        .ok_or(Error::UnknownOs)
+fn get_os_release() -> Result<OsRelease, Error> {
+    OsRelease::new().map_err(|_| Error::ReadError)
 }
 
 // https://blog.tinned-software.net/show-installed-yum-packages-by-size/
@@ -46,14 +41,12 @@ fn show_debian_packages() {
 }
 
 fn main() {
-    match get_os_id() {
-        Ok(id) => {
-            match id.as_str() {
-                "fedora" => show_fedora_packages(),
-                "debian" => show_debian_packages(),
-                _ => println!("ERROR: {:?}", Error::UnknownOs),
-            }
-        }
+    match get_os_release() {
+        // is_like() also matches derivative distros, e.g. Linux Mint (Debian-like)
+        // or CentOS (RHEL-like), without hard-coding every ID.
+        Ok(ref os_release) if os_release.is_like("fedora") => show_fedora_packages(),
+        Ok(ref os_release) if os_release.is_like("debian") => show_debian_packages(),
+        Ok(_) => println!("ERROR: {:?}", Error::UnknownOs),
         Err(e) => println!("ERROR: {:?}", e),
     }
 }
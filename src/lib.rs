@@ -22,15 +22,17 @@
 #![deny(missing_docs)]
 
 use std::convert::From;
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 use std::fs::File;
 use std::io::{BufReader, BufRead};
 use std::path::Path;
 use std::borrow::Cow;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
 const PATHS: [&'static str; 2] = ["/etc/os-release", "/usr/lib/os-release"];
-const QUOTES: [&'static str; 2] = ["\"", "'"];
 
 const COMMON_KEYS: [&'static str; 16] = ["ANSI_COLOR",
                                          "BUG_REPORT_URL",
@@ -107,12 +109,76 @@ impl From<std::io::Error> for OsReleaseError {
 /// A specialized `Result` type for os-release parsing operations.
 pub type Result<T> = std::result::Result<T, OsReleaseError>;
 
-fn trim_quotes(s: &str) -> &str {
-    // TODO: is it malformed if we have only one quote?
-    if QUOTES.iter().any(|q| s.starts_with(q) && s.ends_with(q)) {
-        &s[1..s.len() - 1]
-    } else {
-        s
+/// A boxed iterator over key/value pairs, as returned by [`get_os_release_with_fallback`]
+/// since its backends (strict os-release, lsb-release, single-line release files)
+/// don't share a single concrete iterator type.
+pub type OsReleaseEntries = Box<dyn Iterator<Item = Result<(Cow<'static, str>, String)>>>;
+
+// Resolves the POSIX shell escapes the os-release spec allows inside double quotes:
+// `\\`, `\"`, `\$` and `` \` `` unescape to the literal character. Any other
+// backslash is kept as-is.
+fn unescape_double_quoted(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.peek().cloned() {
+            Some(escaped @ '\\') | Some(escaped @ '"') | Some(escaped @ '$') | Some(escaped @ '`') => {
+                result.push(escaped);
+                chars.next();
+            }
+            _ => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+// Finds the index of the unescaped closing double quote in `s`, scanning from after
+// the opening quote at index 0. A backslash escapes the following character, so an
+// escaped quote (`\"`) doesn't count as closing the string.
+fn find_unescaped_double_quote(s: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (i, c) in s.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match c {
+            '\\' => escaped = true,
+            '"' => return Some(i),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// Strips the surrounding quotes from a value and, for double-quoted values, resolves
+// the escape sequences the os-release spec defines. Single-quoted values are taken
+// literally. An unterminated or stray quote is malformed.
+fn trim_quotes(s: &str) -> Result<String> {
+    match s.chars().next() {
+        Some('"') => {
+            match find_unescaped_double_quote(s) {
+                Some(end) if end == s.len() - 1 => Ok(unescape_double_quoted(&s[1..end])),
+                _ => Err(OsReleaseError::ParseError),
+            }
+        }
+        Some('\'') => {
+            if s.len() < 2 || !s.ends_with('\'') {
+                return Err(OsReleaseError::ParseError);
+            }
+            Ok(s[1..s.len() - 1].to_string())
+        }
+        _ => Ok(s.to_string()),
     }
 }
 
@@ -121,7 +187,7 @@ fn extract_variable_and_value(s: &str) -> Result<(Cow<'static, str>, String)> {
         let var = &s[..equal];
         let var = var.trim();
         let val = &s[equal + 1..];
-        let val = trim_quotes(val.trim()).to_string();
+        let val = trim_quotes(val.trim())?;
 
         if let Some(key) = COMMON_KEYS.iter().find(|&k| *k == var) {
             Ok((Cow::Borrowed(key), val))
@@ -184,3 +250,460 @@ pub fn get_os_release() -> Result<impl Iterator<Item = Result<(Cow<'static, str>
     }
     Err(OsReleaseError::NoFile)
 }
+
+const LSB_RELEASE_PATH: &'static str = "/etc/lsb-release";
+
+const SINGLE_LINE_RELEASE_FILES: [(&'static str, &'static str); 4] = [("alpine", "/etc/alpine-release"),
+                                                                       ("centos", "/etc/centos-release"),
+                                                                       ("rhel", "/etc/redhat-release"),
+                                                                       ("debian", "/etc/debian_version")];
+
+// Maps the keys used by /etc/lsb-release to their os-release equivalents.
+fn remap_lsb_release_key(key: &str) -> Option<&'static str> {
+    match key {
+        "DISTRIB_ID" => Some("ID"),
+        "DISTRIB_RELEASE" => Some("VERSION_ID"),
+        "DISTRIB_CODENAME" => Some("VERSION_CODENAME"),
+        "DISTRIB_DESCRIPTION" => Some("PRETTY_NAME"),
+        _ => None,
+    }
+}
+
+// /etc/lsb-release uses the same `KEY=VALUE` shell-quoted syntax as os-release, so it
+// can be parsed by `parse_os_release_lines` and then have its keys remapped.
+fn parse_lsb_release<P: AsRef<Path>>(
+    path: P)
+    -> Result<impl Iterator<Item = Result<(Cow<'static, str>, String)>>> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let lines = reader.lines().map(|line_result| line_result.map(Cow::Owned));
+
+    Ok(parse_os_release_lines(lines).filter_map(|entry| match entry {
+        Ok((key, value)) => {
+            remap_lsb_release_key(key.as_ref()).map(|mapped| Ok((Cow::Borrowed(mapped), value)))
+        }
+        Err(error) => Some(Err(error)),
+    }))
+}
+
+// Scans for the first `\d+(\.\d+)*` token in `s`, as a simple stand-in for a version
+// number embedded in free-form release text (e.g. "CentOS Linux release 7.9.2009").
+fn extract_version(s: &str) -> Option<String> {
+    let start = s.find(|c: char| c.is_ascii_digit())?;
+    let tail = &s[start..];
+    let bytes = tail.as_bytes();
+    let mut end = 0;
+    while end < bytes.len() {
+        let dot_then_digit = bytes[end] == b'.' &&
+                             bytes.get(end + 1).map_or(false, |b| b.is_ascii_digit());
+        if !(bytes[end].is_ascii_digit() || dot_then_digit) {
+            break;
+        }
+        end += 1;
+    }
+
+    Some(tail[..end].to_string())
+}
+
+// Synthesizes os-release-equivalent key/value pairs from a single-line release file
+// such as /etc/redhat-release, whose entire content is a free-form description.
+fn parse_single_line_release(id: &'static str,
+                              content: &str)
+                              -> Vec<Result<(Cow<'static, str>, String)>> {
+    let content = content.trim();
+    let mut entries = vec![Ok((Cow::Borrowed("ID"), id.to_string())),
+                            Ok((Cow::Borrowed("PRETTY_NAME"), content.to_string()))];
+
+    if let Some(version) = extract_version(content) {
+        entries.push(Ok((Cow::Borrowed("VERSION_ID"), version)));
+    }
+
+    entries
+}
+
+// The actual fallback probing logic, parameterized over the paths to try so it can be
+// exercised against fixture files instead of the hard-coded system paths.
+fn get_os_release_with_fallback_from(
+    os_release_paths: &[&str],
+    lsb_release_path: &str,
+    single_line_release_files: &[(&'static str, &str)])
+    -> Result<OsReleaseEntries> {
+    for file in os_release_paths {
+        if let Ok(os_release) = parse_os_release(file) {
+            let entries: Vec<_> = os_release.collect();
+            return Ok(Box::new(entries.into_iter()));
+        }
+    }
+
+    if let Ok(lsb_release) = parse_lsb_release(lsb_release_path) {
+        // An lsb-release file with none of the `DISTRIB_*` keys we recognize yields no
+        // entries at all; treat that the same as the file not existing so we still
+        // fall through to the single-line release files below.
+        let entries: Vec<_> = lsb_release.collect();
+        if !entries.is_empty() {
+            return Ok(Box::new(entries.into_iter()));
+        }
+    }
+
+    for &(id, path) in single_line_release_files {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            return Ok(Box::new(parse_single_line_release(id, &content).into_iter()));
+        }
+    }
+
+    Err(OsReleaseError::NoFile)
+}
+
+/// Tries the strict [`get_os_release`] lookup first, then falls back to
+/// distro-specific files for hosts that predate `/etc/os-release`: `/etc/lsb-release`
+/// and single-line files like `/etc/alpine-release`, `/etc/centos-release`,
+/// `/etc/redhat-release` and `/etc/debian_version` (mirroring the approach taken by
+/// the `os_info` crate). Each backend yields the same
+/// `Iterator<Item = Result<(Cow<str>, String)>>`, so downstream code doesn't need to
+/// change based on which one was used.
+pub fn get_os_release_with_fallback() -> Result<OsReleaseEntries> {
+    get_os_release_with_fallback_from(&PATHS, LSB_RELEASE_PATH, &SINGLE_LINE_RELEASE_FILES)
+}
+
+/// A strongly-typed view of an os-release file.
+///
+/// Builds on top of [`parse_os_release_lines`] to give every well-known key in
+/// `COMMON_KEYS` its own named field, so callers no longer have to iterate the raw
+/// key/value pairs and match on string keys like `"ID"`. Keys that aren't recognized
+/// are kept in `extra`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsRelease {
+    /// `ID`
+    pub id: Option<String>,
+    /// `ID_LIKE`
+    pub id_like: Option<String>,
+    /// `NAME`
+    pub name: Option<String>,
+    /// `PRETTY_NAME`
+    pub pretty_name: Option<String>,
+    /// `VERSION`
+    pub version: Option<String>,
+    /// `VERSION_ID`
+    pub version_id: Option<String>,
+    /// `VERSION_CODENAME`
+    pub version_codename: Option<String>,
+    /// `VARIANT`
+    pub variant: Option<String>,
+    /// `VARIANT_ID`
+    pub variant_id: Option<String>,
+    /// `CPE_NAME`
+    pub cpe_name: Option<String>,
+    /// `HOME_URL`
+    pub home_url: Option<String>,
+    /// `SUPPORT_URL`
+    pub support_url: Option<String>,
+    /// `BUG_REPORT_URL`
+    pub bug_report_url: Option<String>,
+    /// `PRIVACY_POLICY_URL`
+    pub privacy_policy_url: Option<String>,
+    /// `BUILD_ID`
+    pub build_id: Option<String>,
+    /// `ANSI_COLOR`
+    pub ansi_color: Option<String>,
+    /// Keys not covered by the fields above, keyed by their original name.
+    pub extra: BTreeMap<String, String>,
+}
+
+impl OsRelease {
+    /// Reads and parses the current host's os-release file.
+    ///
+    /// This is a convenience wrapper around [`get_os_release`] that collects the
+    /// key/value pairs into a structured `OsRelease`.
+    ///
+    /// ```no_run
+    /// use rs_release::OsRelease;
+    ///
+    /// match OsRelease::new() {
+    ///     Ok(os_release) => println!("{:?}", os_release.id),
+    ///     Err(e) => println!("Cannot read os-release: {:?}", e),
+    /// }
+    /// ```
+    pub fn new() -> Result<OsRelease> {
+        OsRelease::from_entries(get_os_release()?)
+    }
+
+    /// Reads and parses the current host's os-release file, falling back to
+    /// distro-specific files on hosts that predate `/etc/os-release`.
+    ///
+    /// This is a convenience wrapper around [`get_os_release_with_fallback`] that
+    /// collects the key/value pairs into a structured `OsRelease`.
+    pub fn with_fallback() -> Result<OsRelease> {
+        OsRelease::from_entries(get_os_release_with_fallback()?)
+    }
+
+    /// Builds an `OsRelease` from any iterator of key/value pairs, such as the one
+    /// returned by [`get_os_release_with_fallback`] or [`parse_os_release`]. This is
+    /// the building block behind [`OsRelease::new`], [`OsRelease::with_fallback`] and
+    /// the `FromStr` impl; use it directly when building an `OsRelease` from some
+    /// other custom source of entries.
+    pub fn from_entries<I>(entries: I) -> Result<OsRelease>
+        where I: Iterator<Item = Result<(Cow<'static, str>, String)>>
+    {
+        let mut os_release = OsRelease::default();
+
+        for entry in entries {
+            let (key, value) = entry?;
+
+            match key.as_ref() {
+                "ID" => os_release.id = Some(value),
+                "ID_LIKE" => os_release.id_like = Some(value),
+                "NAME" => os_release.name = Some(value),
+                "PRETTY_NAME" => os_release.pretty_name = Some(value),
+                "VERSION" => os_release.version = Some(value),
+                "VERSION_ID" => os_release.version_id = Some(value),
+                "VERSION_CODENAME" => os_release.version_codename = Some(value),
+                "VARIANT" => os_release.variant = Some(value),
+                "VARIANT_ID" => os_release.variant_id = Some(value),
+                "CPE_NAME" => os_release.cpe_name = Some(value),
+                "HOME_URL" => os_release.home_url = Some(value),
+                "SUPPORT_URL" => os_release.support_url = Some(value),
+                "BUG_REPORT_URL" => os_release.bug_report_url = Some(value),
+                "PRIVACY_POLICY_URL" => os_release.privacy_policy_url = Some(value),
+                "BUILD_ID" => os_release.build_id = Some(value),
+                "ANSI_COLOR" => os_release.ansi_color = Some(value),
+                other => {
+                    os_release.extra.insert(other.to_string(), value);
+                }
+            }
+        }
+
+        Ok(os_release)
+    }
+}
+
+impl OsRelease {
+    /// Splits `ID_LIKE` into its whitespace-separated parent distribution IDs, as
+    /// defined by the os-release specification. Returns an empty `Vec` if `ID_LIKE`
+    /// is absent.
+    pub fn id_like(&self) -> Vec<String> {
+        match self.id_like {
+            Some(ref id_like) => id_like.split_whitespace().map(str::to_string).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns `true` if `id` matches this `ID`, or appears among the `ID_LIKE` parent
+    /// IDs. This lets callers treat derivative distros (e.g. Linux Mint as
+    /// Debian-like, CentOS as RHEL-like) the same as their parent without hard-coding
+    /// every `ID`.
+    pub fn is_like(&self, id: &str) -> bool {
+        self.id.as_deref() == Some(id) || self.id_like().iter().any(|parent| parent == id)
+    }
+
+    /// Parses `VERSION_ID` into an ordered [`Version`], if present and well-formed.
+    pub fn version(&self) -> Option<Version> {
+        self.version_id.as_ref().and_then(|version_id| version_id.parse().ok())
+    }
+}
+
+/// A parsed `VERSION_ID`, ordered so distro versions can be compared directly, e.g.
+/// `os_release.version() >= Some(Version::new(22, 4, 0))`.
+///
+/// Dot-separated numeric components compare element-wise, with missing trailing
+/// components treated as zero (so `22.04` is equal to `22.04.0`, and less than
+/// `22.04.1`). A trailing non-numeric tag (e.g. the `-rc1` in `12-rc1`) is retained and
+/// only breaks ties when the numeric parts are equal.
+#[derive(Debug, Clone)]
+pub struct Version {
+    components: Vec<u64>,
+    tag: Option<String>,
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl Version {
+    /// Builds a `Version` from explicit major/minor/patch numbers, with no tag.
+    pub fn new(major: u64, minor: u64, patch: u64) -> Version {
+        Version {
+            components: vec![major, minor, patch],
+            tag: None,
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = OsReleaseError;
+
+    fn from_str(s: &str) -> Result<Version> {
+        let split_at = s.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(s.len());
+        let (numeric, tag) = s.split_at(split_at);
+
+        if numeric.is_empty() {
+            return Err(OsReleaseError::ParseError);
+        }
+
+        let mut components = Vec::new();
+        for part in numeric.trim_end_matches('.').split('.') {
+            let component = part.parse::<u64>().map_err(|_| OsReleaseError::ParseError)?;
+            components.push(component);
+        }
+
+        let tag = if tag.is_empty() { None } else { Some(tag.to_string()) };
+
+        Ok(Version { components, tag })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> std::cmp::Ordering {
+        let len = self.components.len().max(other.components.len());
+
+        for i in 0..len {
+            let a = self.components.get(i).cloned().unwrap_or(0);
+            let b = other.components.get(i).cloned().unwrap_or(0);
+
+            match a.cmp(&b) {
+                std::cmp::Ordering::Equal => continue,
+                ordering => return ordering,
+            }
+        }
+
+        self.tag.cmp(&other.tag)
+    }
+}
+
+impl FromStr for OsRelease {
+    type Err = OsReleaseError;
+
+    fn from_str(s: &str) -> Result<OsRelease> {
+        OsRelease::from_entries(parse_os_release_str(s))
+    }
+}
+
+static CURRENT: OnceLock<Result<OsRelease>> = OnceLock::new();
+
+/// Returns a process-wide, lazily-initialized parse of the current host's os-release.
+///
+/// The file is read and parsed at most once per process; subsequent calls hand back a
+/// shared reference to the cached result, following the pattern used by the
+/// `os-release` crate. Use [`OsRelease::new`] directly if a fresh parse is needed
+/// instead (e.g. the file is expected to change during the process's lifetime). The
+/// streaming `parse_os_release*` functions are unaffected by this cache.
+pub fn current() -> &'static Result<OsRelease> {
+    CURRENT.get_or_init(OsRelease::new)
+}
+
+// The fallback subsystem's helpers are private, so they're unit-tested here rather
+// than from `tests/test_os_release.rs`.
+#[cfg(test)]
+mod fallback_tests {
+    use super::*;
+
+    #[test]
+    fn extract_version_finds_first_numeric_token() {
+        assert_eq!(Some("7.9.2009".to_string()),
+                   extract_version("CentOS Linux release 7.9.2009 (Core)"));
+        assert_eq!(None, extract_version("No digits here"));
+    }
+
+    #[test]
+    fn parse_single_line_release_synthesizes_id_pretty_name_and_version() {
+        let entries: Vec<_> = parse_single_line_release("centos", "CentOS Linux release 7.9.2009 (Core)")
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert!(entries.contains(&(Cow::Borrowed("ID"), "centos".to_string())));
+        assert!(entries.contains(&(Cow::Borrowed("VERSION_ID"), "7.9.2009".to_string())));
+        assert!(entries.contains(&(Cow::Borrowed("PRETTY_NAME"),
+                                    "CentOS Linux release 7.9.2009 (Core)".to_string())));
+    }
+
+    #[test]
+    fn parse_single_line_release_without_a_version_omits_version_id() {
+        let entries: Vec<_> = parse_single_line_release("debian", "unknown")
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert!(!entries.iter().any(|(key, _)| key == "VERSION_ID"));
+    }
+
+    // Creates a uniquely-named temp file under `name`, writes `content` to it and
+    // returns its path as a `String`. Panics on failure, like the other fixture setup
+    // this crate's tests do.
+    fn write_temp_file(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("rs_release_test_{}_{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn fallback_prefers_os_release_then_lsb_release_then_single_line_files() {
+        let os_release_path = write_temp_file("os-release", "ID=fedora\n");
+        let lsb_release_path = write_temp_file("lsb-release", "DISTRIB_ID=Ubuntu\n");
+        let debian_version_path = write_temp_file("debian_version", "11.6\n");
+
+        let single_line_release_files = [("debian", debian_version_path.as_str())];
+
+        let entries: std::collections::HashMap<_, _> =
+            get_os_release_with_fallback_from(&[os_release_path.as_str()],
+                                               &lsb_release_path,
+                                               &single_line_release_files)
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+        assert_eq!("fedora", entries["ID"]);
+
+        let entries: std::collections::HashMap<_, _> =
+            get_os_release_with_fallback_from(&["/nonexistent/os-release"],
+                                               &lsb_release_path,
+                                               &single_line_release_files)
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+        assert_eq!("Ubuntu", entries["ID"]);
+
+        let entries: std::collections::HashMap<_, _> =
+            get_os_release_with_fallback_from(&["/nonexistent/os-release"],
+                                               "/nonexistent/lsb-release",
+                                               &single_line_release_files)
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+        assert_eq!("debian", entries["ID"]);
+        assert_eq!("11.6", entries["VERSION_ID"]);
+
+        std::fs::remove_file(&os_release_path).ok();
+        std::fs::remove_file(&lsb_release_path).ok();
+        std::fs::remove_file(&debian_version_path).ok();
+    }
+
+    #[test]
+    fn fallback_skips_an_lsb_release_with_no_recognized_keys() {
+        let lsb_release_path = write_temp_file("lsb-release-unrecognized", "SOME_OTHER_KEY=value\n");
+        let debian_version_path = write_temp_file("debian_version_fallthrough", "11.6\n");
+
+        let single_line_release_files = [("debian", debian_version_path.as_str())];
+
+        let entries: std::collections::HashMap<_, _> =
+            get_os_release_with_fallback_from(&["/nonexistent/os-release"],
+                                               &lsb_release_path,
+                                               &single_line_release_files)
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+
+        assert_eq!("debian", entries["ID"]);
+
+        std::fs::remove_file(&lsb_release_path).ok();
+        std::fs::remove_file(&debian_version_path).ok();
+    }
+}
@@ -2,7 +2,7 @@ extern crate rs_release;
 
 use std::collections::HashMap;
 
-use rs_release::{OsReleaseError, parse_os_release, parse_os_release_str};
+use rs_release::{OsRelease, OsReleaseError, Version, parse_os_release, parse_os_release_str};
 
 #[test]
 fn fails_on_io_errors() {
@@ -105,3 +105,100 @@ CPE_NAME=        cpe:/o:fedoraproject:fedora:24   ";
     let os_release_empty = os_release_empty.unwrap();
     assert_eq!(0, os_release_empty.len());
 }
+
+#[test]
+fn unescapes_double_quoted_values() {
+    let data = r#"PRETTY_NAME="Foo \"Bar\"""#;
+    let os_release: HashMap<_, _> = parse_os_release_str(data).collect::<Result<_, _>>().unwrap();
+    assert_eq!("Foo \"Bar\"", os_release["PRETTY_NAME"]);
+
+    let data = r#"HOME_URL="https://example.com/\$user""#;
+    let os_release: HashMap<_, _> = parse_os_release_str(data).collect::<Result<_, _>>().unwrap();
+    assert_eq!("https://example.com/$user", os_release["HOME_URL"]);
+}
+
+#[test]
+fn single_quoted_values_are_literal() {
+    let data = r"NAME='Foo \'Bar\'\$'";
+    let os_release: HashMap<_, _> = parse_os_release_str(data).collect::<Result<_, _>>().unwrap();
+    assert_eq!(r"Foo \'Bar\'\$", os_release["NAME"]);
+}
+
+#[test]
+fn fails_on_unterminated_or_stray_quotes() {
+    for data in &["NAME=\"Foo", "NAME='Foo", "NAME=\"", "NAME='"] {
+        let os_release: Result<HashMap<_, _>, _> = parse_os_release_str(data).collect();
+        assert_eq!(Some(OsReleaseError::ParseError), os_release.err());
+    }
+}
+
+#[test]
+fn fails_when_only_closing_quote_is_escaped() {
+    // The trailing `\"` is an escaped quote, so the string was never actually closed.
+    let data = r#"NAME="a\""#;
+    let os_release: Result<HashMap<_, _>, _> = parse_os_release_str(data).collect();
+    assert_eq!(Some(OsReleaseError::ParseError), os_release.err());
+}
+
+#[test]
+fn from_entries_is_public_and_builds_an_os_release_from_any_source() {
+    let os_release = OsRelease::from_entries(parse_os_release_str("ID=fedora\n")).unwrap();
+    assert_eq!(Some("fedora".to_string()), os_release.id);
+}
+
+#[test]
+fn os_release_from_str_populates_known_fields() {
+    let data = "ID=fedora\nNAME=Fedora\nSOME_EXTRA_KEY=value";
+    let os_release: OsRelease = data.parse().unwrap();
+
+    assert_eq!(Some("fedora".to_string()), os_release.id);
+    assert_eq!(Some("Fedora".to_string()), os_release.name);
+    assert_eq!(Some(&"value".to_string()), os_release.extra.get("SOME_EXTRA_KEY"));
+}
+
+#[test]
+fn os_release_from_str_fails_on_parse_errors() {
+    assert_eq!(Some(OsReleaseError::ParseError), "SOMETHING".parse::<OsRelease>().err());
+}
+
+#[test]
+fn id_like_splits_on_whitespace() {
+    let os_release: OsRelease = "ID=linuxmint\nID_LIKE=ubuntu debian\n".parse().unwrap();
+    assert_eq!(vec!["ubuntu".to_string(), "debian".to_string()], os_release.id_like());
+
+    let os_release: OsRelease = "ID=fedora\n".parse().unwrap();
+    assert!(os_release.id_like().is_empty());
+}
+
+#[test]
+fn version_parses_dotted_numeric_forms() {
+    assert_eq!(Version::new(22, 4, 0), "22.04".parse().unwrap());
+    assert_eq!(Version::new(24, 0, 0), "24".parse().unwrap());
+    assert!("not-a-version".parse::<Version>().is_err());
+}
+
+#[test]
+fn version_compares_missing_components_as_zero() {
+    assert!("22.04".parse::<Version>().unwrap() < "22.04.1".parse::<Version>().unwrap());
+    assert_eq!("22.04".parse::<Version>().unwrap(), "22.04.0".parse::<Version>().unwrap());
+    assert!("8.5.1".parse::<Version>().unwrap() > Version::new(8, 5, 0));
+}
+
+#[test]
+fn os_release_exposes_parsed_version() {
+    let os_release: OsRelease = "VERSION_ID=22.04\n".parse().unwrap();
+    assert!(os_release.version() >= Some(Version::new(22, 4, 0)));
+
+    let os_release: OsRelease = "VERSION_ID=not-a-version\n".parse().unwrap();
+    assert_eq!(None, os_release.version());
+}
+
+#[test]
+fn is_like_matches_id_and_id_like() {
+    let os_release: OsRelease = "ID=linuxmint\nID_LIKE=ubuntu debian\n".parse().unwrap();
+
+    assert!(os_release.is_like("linuxmint"));
+    assert!(os_release.is_like("debian"));
+    assert!(os_release.is_like("ubuntu"));
+    assert!(!os_release.is_like("fedora"));
+}